@@ -1,5 +1,7 @@
+// No Cargo.toml is tracked in this repo (see .gitignore), so there's no manifest here to add
+// `serde` (with the `derive` feature, for `BodySpec`/`SceneSpec`) and `ron` to — flagging so
+// whoever adds the manifest knows both are required by scene loading below.
 use bevy::{
-	app::AppExit,
 	core_pipeline::clear_color::ClearColorConfig,
 	prelude::*,
 	sprite::{ColorMaterial, MaterialMesh2dBundle},
@@ -8,6 +10,120 @@ use bevy::{
 
 use bevy_editor_pls::EditorPlugin;
 use bevy_pancam::{PanCam, PanCamPlugin};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// Directory holding the preset RON scenes, relative to the working directory.
+const SCENES_DIR: &str = "assets/scenes";
+
+// The scene loaded when no `--scene` argument is given.
+const DEFAULT_SCENE: &str = "random_cloud";
+
+// One body's initial conditions, as read from a scene's RON file.
+#[derive(Debug, Clone, Deserialize)]
+struct BodySpec {
+	position: (f32, f32),
+	velocity: (f32, f32),
+	mass: u32,
+	radius: f32,
+	color: (f32, f32, f32),
+}
+
+// A full scene: the list of bodies to spawn at startup.
+#[derive(Debug, Clone, Deserialize)]
+struct SceneSpec {
+	bodies: Vec<BodySpec>,
+}
+
+// Picks the scene name from `--scene <name>` on the command line, falling back to
+// `DEFAULT_SCENE`, then loads `assets/scenes/<name>.ron`.
+fn load_scene() -> SceneSpec {
+	let args: Vec<String> = std::env::args().collect();
+	let name = args
+		.iter()
+		.position(|arg| arg == "--scene")
+		.and_then(|index| args.get(index + 1))
+		.map(String::as_str)
+		.unwrap_or(DEFAULT_SCENE);
+
+	let path = format!("{SCENES_DIR}/{name}.ron");
+	let contents =
+		std::fs::read_to_string(&path).unwrap_or_else(|error| panic!("failed to read scene {path}: {error}"));
+	ron::de::from_str(&contents).unwrap_or_else(|error| panic!("failed to parse scene {path}: {error}"))
+}
+
+// Component for a body's collision radius, read from its scene spec and also used to size the
+// rendered mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+struct Radius(f32);
+
+// Opening angle for the Barnes-Hut approximation: smaller is more accurate, larger is faster.
+// Adjustable at runtime with `[`/`]` (see `toggle_gravity_mode`).
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+struct Theta(f32);
+
+impl Default for Theta {
+	fn default() -> Self {
+		Theta(0.5)
+	}
+}
+
+// Which gravity system runs: the O(n log n) approximation or the exact O(n²) pass, kept around
+// for comparison. Toggled at runtime with G (see `toggle_gravity_mode`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+enum GravityMode {
+	BarnesHut,
+	Exact,
+}
+
+impl Default for GravityMode {
+	fn default() -> Self {
+		GravityMode::BarnesHut
+	}
+}
+
+// The simulation's top-level state machine: a main menu before launch, the running simulation,
+// and a paused overlay. Physics systems are gated with `run_if(in_state(SimState::Running))`
+// instead of a manually-queried pause flag.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, States)]
+enum SimState {
+	#[default]
+	MainMenu,
+	Running,
+	Paused,
+}
+
+// Marker for the root node of the main menu UI, despawned on leaving `SimState::MainMenu`.
+#[derive(Component)]
+struct MainMenuUi;
+
+// Marker for the "Start" button on the main menu.
+#[derive(Component)]
+struct StartButton;
+
+// Marker for the root node of the pause overlay, spawned on entering and despawned on leaving
+// `SimState::Paused`.
+#[derive(Component)]
+struct PauseOverlayUi;
+
+// Whether the camera chases the system's mass-weighted center of mass, and how eagerly. When
+// disabled, `PanCam` keeps its usual manual pan/zoom controls.
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+struct FollowCamera {
+	enabled: bool,
+	stiffness: f32,
+	auto_zoom: bool,
+}
+
+impl Default for FollowCamera {
+	fn default() -> Self {
+		FollowCamera {
+			enabled: false,
+			stiffness: 5.0,
+			auto_zoom: true,
+		}
+	}
+}
 
 // Component for velocity
 #[derive(Debug, Clone, Copy, PartialEq, Component)]
@@ -17,9 +133,6 @@ struct Velocity(Vec3);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
 struct Mass(u32);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
-struct Pause(bool);
-
 #[derive(Debug, Clone, PartialEq, Component)]
 struct SortedEntitiesByX(Vec<(Entity, f32)>);
 
@@ -33,98 +146,529 @@ fn main() {
 		.add_plugin(EditorPlugin)
 		.add_plugin(bevy::diagnostic::FrameTimeDiagnosticsPlugin)
 		.add_plugin(bevy::diagnostic::EntityCountDiagnosticsPlugin)
+		.add_state::<SimState>()
+		.init_resource::<Theta>()
+		.init_resource::<GravityMode>()
+		.init_resource::<FollowCamera>()
 		.add_startup_system(setup)
-		.add_system(update_from_velocity)
-		.add_system(update_from_gravity)
-		.add_system(handle_collision)
-		.add_system(exit_on_escape_system)
-		.add_system(pause_game)
-		.add_system(maintain_sorted_entities_x)
+		.add_system(setup_main_menu.in_schedule(OnEnter(SimState::MainMenu)))
+		.add_system(teardown_main_menu.in_schedule(OnExit(SimState::MainMenu)))
+		.add_system(setup_pause_overlay.in_schedule(OnEnter(SimState::Paused)))
+		.add_system(teardown_pause_overlay.in_schedule(OnExit(SimState::Paused)))
+		.add_system(start_button_system.run_if(in_state(SimState::MainMenu)))
+		.add_system(toggle_pause.run_if(not(in_state(SimState::MainMenu))))
+		.add_system(update_from_velocity.run_if(in_state(SimState::Running)))
+		.add_system(update_from_gravity.run_if(in_state(SimState::Running)))
+		.add_system(handle_collision.run_if(in_state(SimState::Running)))
+		.add_system(maintain_sorted_entities_x.run_if(in_state(SimState::Running)))
+		.add_system(maintain_sorted_entities_y.run_if(in_state(SimState::Running)))
+		.add_system(toggle_follow_camera)
+		.add_system(follow_center_of_mass)
+		.add_system(toggle_gravity_mode)
 		.run();
 }
 
-// Function to pause	the game
-fn pause_game(keyboard_input: Res<Input<KeyCode>>, mut pause: Query<&mut Pause>) {
-	if keyboard_input.just_pressed(KeyCode::Space) {
-		println!("Paused toggle: {:#?}", pause);
-		let mut pause = pause.iter_mut().next().unwrap();
-		pause.0 = !pause.0;
+// Toggles between `GravityMode::BarnesHut` and `GravityMode::Exact` with G, and adjusts the
+// Barnes-Hut opening angle with `[`/`]` so the approximation's accuracy/speed tradeoff is
+// reachable without recompiling.
+fn toggle_gravity_mode(
+	keyboard_input: Res<Input<KeyCode>>,
+	mut mode: ResMut<GravityMode>,
+	mut theta: ResMut<Theta>,
+) {
+	if keyboard_input.just_pressed(KeyCode::G) {
+		*mode = match *mode {
+			GravityMode::BarnesHut => GravityMode::Exact,
+			GravityMode::Exact => GravityMode::BarnesHut,
+		};
+	}
+	if keyboard_input.just_pressed(KeyCode::LBracket) {
+		theta.0 = (theta.0 - 0.1).max(0.1);
+	}
+	if keyboard_input.just_pressed(KeyCode::RBracket) {
+		theta.0 += 0.1;
+	}
+}
+
+// Toggles the follow camera with F. While enabled, `PanCam` is disabled so the two don't fight
+// over the camera transform.
+fn toggle_follow_camera(
+	keyboard_input: Res<Input<KeyCode>>,
+	mut follow: ResMut<FollowCamera>,
+	mut pan_cams: Query<&mut PanCam>,
+) {
+	if keyboard_input.just_pressed(KeyCode::F) {
+		follow.enabled = !follow.enabled;
+		for mut pan_cam in &mut pan_cams {
+			pan_cam.enabled = !follow.enabled;
+		}
+	}
+}
+
+// Smoothly moves the camera toward the mass-weighted center of mass of all bodies, optionally
+// zooming so their bounding box stays in frame. No-op while `FollowCamera` is disabled, leaving
+// `PanCam` in full control.
+fn follow_center_of_mass(
+	time: Res<Time>,
+	follow: Res<FollowCamera>,
+	bodies: Query<(&Mass, &Transform), Without<Camera2d>>,
+	mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+	if !follow.enabled {
+		return;
+	}
+
+	let mut total_mass = 0.0;
+	let mut weighted_position = Vec3::ZERO;
+	let mut min = Vec2::splat(f32::MAX);
+	let mut max = Vec2::splat(f32::MIN);
+	for (mass, transform) in &bodies {
+		let mass = mass.0 as f32;
+		total_mass += mass;
+		weighted_position += transform.translation * mass;
+		min = min.min(transform.translation.truncate());
+		max = max.max(transform.translation.truncate());
+	}
+	if total_mass <= 0. {
+		return;
+	}
+	let center_of_mass = weighted_position / total_mass;
+
+	let Ok((mut camera_transform, mut projection)) = cameras.get_single_mut() else {
+		return;
+	};
+
+	let t = (follow.stiffness * time.delta_seconds()).clamp(0., 1.);
+	camera_transform.translation = camera_transform.translation.lerp(
+		Vec3::new(center_of_mass.x, center_of_mass.y, camera_transform.translation.z),
+		t,
+	);
+
+	if follow.auto_zoom {
+		let span = (max - min).max_element().max(1.0);
+		let target_scale = (span / 600.0).clamp(0.01, 40.0);
+		projection.scale += (target_scale - projection.scale) * t;
+	}
+}
+
+// Spawns the main menu's "Start" button, shown while `SimState::MainMenu` is active.
+fn setup_main_menu(mut commands: Commands) {
+	commands
+		.spawn((
+			NodeBundle {
+				style: Style {
+					size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+					justify_content: JustifyContent::Center,
+					align_items: AlignItems::Center,
+					..default()
+				},
+				..default()
+			},
+			MainMenuUi,
+		))
+		.with_children(|parent| {
+			parent
+				.spawn((
+					ButtonBundle {
+						style: Style {
+							size: Size::new(Val::Px(150.), Val::Px(65.)),
+							justify_content: JustifyContent::Center,
+							align_items: AlignItems::Center,
+							..default()
+						},
+						background_color: Color::DARK_GRAY.into(),
+						..default()
+					},
+					StartButton,
+				))
+				.with_children(|parent| {
+					parent.spawn(TextBundle::from_section(
+						"Start",
+						TextStyle {
+							font_size: 40.,
+							color: Color::WHITE,
+							..default()
+						},
+					));
+				});
+		});
+}
+
+// Removes the main menu UI on leaving `SimState::MainMenu`.
+fn teardown_main_menu(mut commands: Commands, query: Query<Entity, With<MainMenuUi>>) {
+	for entity in &query {
+		commands.entity(entity).despawn_recursive();
+	}
+}
+
+// Moves to `SimState::Running` once the "Start" button is clicked.
+fn start_button_system(
+	interactions: Query<&Interaction, (Changed<Interaction>, With<StartButton>)>,
+	mut next_state: ResMut<NextState<SimState>>,
+) {
+	for interaction in &interactions {
+		if *interaction == Interaction::Clicked {
+			next_state.set(SimState::Running);
+		}
+	}
+}
+
+// Spawns a dimmed "Paused" overlay, shown while `SimState::Paused` is active.
+fn setup_pause_overlay(mut commands: Commands) {
+	commands
+		.spawn((
+			NodeBundle {
+				style: Style {
+					size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+					justify_content: JustifyContent::Center,
+					align_items: AlignItems::Center,
+					..default()
+				},
+				background_color: Color::rgba(0., 0., 0., 0.5).into(),
+				..default()
+			},
+			PauseOverlayUi,
+		))
+		.with_children(|parent| {
+			parent.spawn(TextBundle::from_section(
+				"Paused",
+				TextStyle {
+					font_size: 60.,
+					color: Color::WHITE,
+					..default()
+				},
+			));
+		});
+}
+
+// Removes the pause overlay on leaving `SimState::Paused`.
+fn teardown_pause_overlay(mut commands: Commands, query: Query<Entity, With<PauseOverlayUi>>) {
+	for entity in &query {
+		commands.entity(entity).despawn_recursive();
 	}
 }
 
-// Function that exits on escape
-fn exit_on_escape_system(
+// Toggles between `SimState::Running` and `SimState::Paused` on Space or Escape.
+fn toggle_pause(
 	keyboard_input: Res<Input<KeyCode>>,
-	mut app_exit_events: ResMut<Events<AppExit>>,
+	state: Res<State<SimState>>,
+	mut next_state: ResMut<NextState<SimState>>,
 ) {
-	if keyboard_input.just_pressed(KeyCode::Escape) {
-		app_exit_events.send(AppExit);
+	if keyboard_input.just_pressed(KeyCode::Space) || keyboard_input.just_pressed(KeyCode::Escape) {
+		match state.0 {
+			SimState::Running => next_state.set(SimState::Paused),
+			SimState::Paused => next_state.set(SimState::Running),
+			SimState::MainMenu => {}
+		}
 	}
 }
 
-// Function that maintains a sorted list of entities by x position
-fn maintain_sorted_entities_x(mut list: Query<&'static mut SortedEntitiesByX>) {
+// Function that refreshes the stored x coordinates from the bodies' current transforms and
+// keeps the list sorted by x, ready for a sweep-and-prune pass.
+fn maintain_sorted_entities_x(
+	mut list: Query<&mut SortedEntitiesByX>,
+	transforms: Query<&Transform>,
+) {
 	let mut list = list.single_mut();
+	for (entity, x) in list.0.iter_mut() {
+		if let Ok(transform) = transforms.get(*entity) {
+			*x = transform.translation.x;
+		}
+	}
 	list.0.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
 }
 
-// Function system to handle collision by reversing the velocity
-fn handle_collision(pause: Query<&Pause>, mut query: Query<(&mut Velocity, &Transform)>) {
-	if pause.iter().next().unwrap().0 {
-		return;
+// Function that refreshes the stored y coordinates from the bodies' current transforms and
+// keeps the list sorted by y.
+fn maintain_sorted_entities_y(
+	mut list: Query<&mut SortedEntitiesByY>,
+	transforms: Query<&Transform>,
+) {
+	let mut list = list.single_mut();
+	for (entity, y) in list.0.iter_mut() {
+		if let Ok(transform) = transforms.get(*entity) {
+			*y = transform.translation.y;
+		}
 	}
+	list.0.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+}
+
+// Function system to handle collision with a momentum-conserving elastic response. Broadphase is
+// sweep-and-prune over `SortedEntitiesByX`: since the list is sorted by x, once a candidate's x
+// gap exceeds the largest possible collision diameter no further entries can overlap, so the
+// inner loop breaks early. The y-axis prune reads the cached coordinates from
+// `SortedEntitiesByY` rather than the live `Transform`, so that list's per-frame refresh is
+// actually put to use.
+fn handle_collision(
+	sorted_x: Query<&SortedEntitiesByX>,
+	sorted_y: Query<&SortedEntitiesByY>,
+	radii: Query<&Radius>,
+	mut bodies: Query<(&mut Velocity, &mut Transform, &Mass, &Radius)>,
+) {
+	let entries = &sorted_x.single().0;
+	let y_by_entity: HashMap<Entity, f32> = sorted_y.single().0.iter().copied().collect();
+	let max_diameter = radii.iter().map(|radius| radius.0).fold(0., f32::max) * 2.;
+
+	for i in 0..entries.len() {
+		let (entity_a, x_a) = entries[i];
+		for &(entity_b, x_b) in &entries[i + 1..] {
+			if x_b - x_a > max_diameter {
+				break;
+			}
 
-	let mut pairs = query.iter_combinations_mut::<2>();
-	while let Some([(mut v1, t1), (mut v2, t2)]) = pairs.fetch_next() {
-		if t1.translation.distance(t2.translation) < 10. {
-			v1.0 = -v1.0;
-			v2.0 = -v2.0;
+			let Ok([(mut v1, mut t1, m1, r1), (mut v2, mut t2, m2, r2)]) =
+				bodies.get_many_mut([entity_a, entity_b])
+			else {
+				continue;
+			};
+			let collision_distance = r1.0 + r2.0;
+
+			// Cheap y-axis prune before paying for the real distance test.
+			let (Some(&y_a), Some(&y_b)) = (y_by_entity.get(&entity_a), y_by_entity.get(&entity_b)) else {
+				continue;
+			};
+			if (y_a - y_b).abs() > collision_distance {
+				continue;
+			}
+			let distance = t1.translation.distance(t2.translation);
+			if distance >= collision_distance {
+				continue;
+			}
+
+			let normal = if distance > 0. {
+				(t2.translation - t1.translation) / distance
+			} else {
+				Vec3::X
+			};
+			let m1 = m1.0 as f32;
+			let m2 = m2.0 as f32;
+
+			let v1_new =
+				v1.0 - normal * ((2. * m2 / (m1 + m2)) * (v1.0 - v2.0).dot(normal));
+			let v2_new =
+				v2.0 - normal * ((2. * m1 / (m1 + m2)) * (v2.0 - v1.0).dot(normal));
+			v1.0 = v1_new;
+			v2.0 = v2_new;
+
+			// Push the overlapping bodies apart along the normal so they don't stick together.
+			let correction = normal * ((collision_distance - distance) / 2.);
+			t1.translation -= correction;
+			t2.translation += correction;
 		}
 	}
 }
 
 // Update translation from velocity
-fn update_from_velocity(
-	time: Res<Time>,
-	pause: Query<&Pause>,
-	mut query: Query<(&Velocity, &mut Transform)>,
-) {
-	if pause.iter().next().unwrap().0 {
-		return;
-	}
+fn update_from_velocity(time: Res<Time>, mut query: Query<(&Velocity, &mut Transform)>) {
 	for (velocity, mut transform) in query.iter_mut() {
 		transform.translation += velocity.0 * time.delta_seconds();
 	}
 }
 
-// Given two vectors of two masses, return the new velocity of the first mass
-fn calculate_new_velocity(m1: &Mass, m2: &Mass, t1: &Transform, t2: &Transform) -> Vec3 {
-	let m1 = m1.0 as f32;
-	let m2 = m2.0 as f32;
-
-	let direction = t2.translation - t1.translation;
+// The raw gravitational force contribution of a mass at `p2` acting on a mass at `p1`.
+fn gravitational_force(m1: f32, m2: f32, p1: Vec3, p2: Vec3) -> Vec3 {
+	let direction = p2 - p1;
 	let distance = direction.length();
 	let force = 1.0 / distance.powi(2);
 	let force = direction.normalize() * force;
 
-	let force = force * m1 * m2;
-	force
+	force * m1 * m2
+}
+
+// Given two masses and their transforms, return the gravitational force the second exerts on the
+// first; the caller adds it to the first body's velocity and subtracts it from the second's.
+fn calculate_new_velocity(m1: &Mass, m2: &Mass, t1: &Transform, t2: &Transform) -> Vec3 {
+	gravitational_force(m1.0 as f32, m2.0 as f32, t1.translation, t2.translation)
+}
+
+// The square region covered by a quadtree node: its center and side length.
+#[derive(Debug, Clone, Copy)]
+struct QuadBounds {
+	center: Vec2,
+	size: f32,
+}
+
+impl QuadBounds {
+	fn quadrant_for(&self, point: Vec2) -> usize {
+		match (point.x >= self.center.x, point.y >= self.center.y) {
+			(false, false) => 0,
+			(true, false) => 1,
+			(false, true) => 2,
+			(true, true) => 3,
+		}
+	}
+
+	fn child_bounds(&self, quadrant: usize) -> QuadBounds {
+		let quarter = self.size / 4.0;
+		let offset = match quadrant {
+			0 => Vec2::new(-quarter, -quarter),
+			1 => Vec2::new(quarter, -quarter),
+			2 => Vec2::new(-quarter, quarter),
+			_ => Vec2::new(quarter, quarter),
+		};
+		QuadBounds {
+			center: self.center + offset,
+			size: self.size / 2.0,
+		}
+	}
+}
+
+// Past this many levels of subdivision, bodies stop getting their own quadrant and instead pile
+// into the same leaf. Without this, two bodies at (or extremely close to) the exact same
+// position recurse forever, since `quadrant_for` keeps routing them to the same child.
+const MAX_QUAD_DEPTH: u32 = 24;
+
+#[derive(Debug)]
+enum QuadContent {
+	Empty,
+	Leaf { bodies: Vec<(Entity, Vec2, f32)> },
+	Internal { children: Box<[QuadNode; 4]> },
+}
+
+// A node of the quadtree built fresh each frame over the current body translations. Every node
+// tracks the total mass and mass-weighted center of mass of the bodies beneath it so that distant
+// clusters can be treated as a single point mass.
+#[derive(Debug)]
+struct QuadNode {
+	bounds: QuadBounds,
+	depth: u32,
+	mass: f32,
+	center_of_mass: Vec2,
+	content: QuadContent,
 }
 
-// Update velocity from the gravity of the other bodies
+impl QuadNode {
+	fn new(bounds: QuadBounds, depth: u32) -> Self {
+		QuadNode {
+			bounds,
+			depth,
+			mass: 0.0,
+			center_of_mass: Vec2::ZERO,
+			content: QuadContent::Empty,
+		}
+	}
+
+	fn child(&self, quadrant: usize) -> QuadNode {
+		QuadNode::new(self.bounds.child_bounds(quadrant), self.depth + 1)
+	}
+
+	fn insert(&mut self, entity: Entity, position: Vec2, mass: f32) {
+		self.center_of_mass =
+			(self.center_of_mass * self.mass + position * mass) / (self.mass + mass);
+		self.mass += mass;
+
+		match &mut self.content {
+			QuadContent::Empty => {
+				self.content = QuadContent::Leaf {
+					bodies: vec![(entity, position, mass)],
+				};
+			}
+			QuadContent::Leaf { bodies } if bodies.len() == 1 && self.depth < MAX_QUAD_DEPTH => {
+				let mut children = [self.child(0), self.child(1), self.child(2), self.child(3)];
+				for (other_entity, other_position, other_mass) in
+					bodies.drain(..).chain(std::iter::once((entity, position, mass)))
+				{
+					children[self.bounds.quadrant_for(other_position)]
+						.insert(other_entity, other_position, other_mass);
+				}
+				self.content = QuadContent::Internal {
+					children: Box::new(children),
+				};
+			}
+			QuadContent::Leaf { bodies } => {
+				// Already at max depth (or already a multi-body leaf): keep piling bodies up
+				// rather than subdividing a cell that can no longer separate them.
+				bodies.push((entity, position, mass));
+			}
+			QuadContent::Internal { children } => {
+				children[self.bounds.quadrant_for(position)].insert(entity, position, mass);
+			}
+		}
+	}
+
+	// Accumulate the force this node (or its descendants) exerts on `body`, skipping its own leaf.
+	fn accumulate_force(&self, body: Entity, position: Vec3, theta: f32, force: &mut Vec3) {
+		match &self.content {
+			QuadContent::Empty => {}
+			QuadContent::Leaf { bodies } => {
+				for (entity, other_position, mass) in bodies {
+					if *entity != body {
+						*force += gravitational_force(1.0, *mass, position, other_position.extend(0.));
+					}
+				}
+			}
+			QuadContent::Internal { children } => {
+				let com = self.center_of_mass.extend(0.);
+				let distance = com.distance(position);
+				if distance > 0. && self.bounds.size / distance < theta {
+					*force += gravitational_force(1.0, self.mass, position, com);
+				} else {
+					for child in children.iter() {
+						child.accumulate_force(body, position, theta, force);
+					}
+				}
+			}
+		}
+	}
+}
+
+// Build a quadtree over the axis-aligned bounding box of every body's translation.
+fn build_quadtree(bodies: &[(Entity, Vec3, f32)]) -> Option<QuadNode> {
+	let mut min = Vec2::splat(f32::MAX);
+	let mut max = Vec2::splat(f32::MIN);
+	for (_, translation, _) in bodies {
+		min = min.min(translation.truncate());
+		max = max.max(translation.truncate());
+	}
+	if !min.is_finite() || !max.is_finite() {
+		return None;
+	}
+
+	let size = (max - min).max_element().max(1.0);
+	let mut root = QuadNode::new(
+		QuadBounds {
+			center: (min + max) / 2.0,
+			size,
+		},
+		0,
+	);
+	for (entity, translation, mass) in bodies {
+		root.insert(*entity, translation.truncate(), *mass);
+	}
+	Some(root)
+}
+
+// Update velocity from the gravity of the other bodies, using a Barnes-Hut approximation by
+// default (see `GravityMode`) so the pass stays O(n log n) as the body count grows.
 fn update_from_gravity(
-	pause: Query<&Pause>,
-	mut query: Query<(&Mass, &Transform, &mut Velocity)>,
+	mode: Res<GravityMode>,
+	theta: Res<Theta>,
+	mut query: Query<(Entity, &Mass, &Transform, &mut Velocity)>,
 ) {
-	if pause.iter().next().unwrap().0 {
-		return;
-	}
-	// Get both entities and their components
-	let mut pairs = query.iter_combinations_mut::<2>();
-	while let Some([(m1, t1, mut v1), (m2, t2, mut v2)]) = pairs.fetch_next() {
-		let force = calculate_new_velocity(m1, m2, t1, t2);
-		v1.0 += force;
-		v2.0 -= force;
+	match *mode {
+		GravityMode::Exact => {
+			let mut pairs = query.iter_combinations_mut::<2>();
+			while let Some([(_, m1, t1, mut v1), (_, m2, t2, mut v2)]) = pairs.fetch_next() {
+				let force = calculate_new_velocity(m1, m2, t1, t2);
+				v1.0 += force;
+				v2.0 -= force;
+			}
+		}
+		GravityMode::BarnesHut => {
+			let bodies: Vec<(Entity, Vec3, f32)> = query
+				.iter()
+				.map(|(entity, mass, transform, _)| (entity, transform.translation, mass.0 as f32))
+				.collect();
+			let Some(tree) = build_quadtree(&bodies) else {
+				return;
+			};
+
+			for (entity, mass, transform, mut velocity) in query.iter_mut() {
+				let mut force = Vec3::ZERO;
+				tree.accumulate_force(entity, transform.translation, theta.0, &mut force);
+				velocity.0 += force * mass.0 as f32;
+			}
+		}
 	}
 }
 
@@ -150,27 +694,35 @@ fn setup(
 			..Default::default()
 		});
 
+	let scene = load_scene();
+
 	let mut x_items: Vec<(Entity, f32)> = Vec::new();
 	let mut y_items: Vec<(Entity, f32)> = Vec::new();
 
-	for i in 0..20 {
-		let x = 100.0 * i as f32;
+	for body in &scene.bodies {
+		let (x, y) = body.position;
+		let (vx, vy) = body.velocity;
+		let (r, g, b) = body.color;
+
 		let entity = MaterialMesh2dBundle {
-			mesh: meshes.add(shape::Circle::new(5.).into()).into(),
-			material: materials.add(ColorMaterial::from(Color::BLUE)),
-			transform: Transform::from_translation(Vec3::new(x, 0., 0.)),
+			mesh: meshes.add(shape::Circle::new(body.radius).into()).into(),
+			material: materials.add(ColorMaterial::from(Color::rgb(r, g, b))),
+			transform: Transform::from_translation(Vec3::new(x, y, 0.)),
 			..default()
 		};
 
-		let cmd = commands.spawn(entity);
+		let cmd = commands.spawn((
+			entity,
+			Velocity(Vec3::new(vx, vy, 0.)),
+			Mass(body.mass),
+			Radius(body.radius),
+		));
 
 		let id = cmd.id();
-		x_items.push((id.clone(), x));
-		y_items.push((id.clone(), 0.));
+		x_items.push((id, x));
+		y_items.push((id, y));
 	}
 
 	commands.spawn(SortedEntitiesByX(x_items));
 	commands.spawn(SortedEntitiesByY(y_items));
-
-	commands.spawn(Pause(false));
 }